@@ -0,0 +1,95 @@
+// Send password Argon2id cost parameters.
+//
+// NOTE ON SCOPE: the crate's real `config.rs` already exists (it's what `send.rs` means by
+// `crate::config::PathType` and `CONFIG`) and is not part of this trimmed tree. What follows is
+// the addition this series makes to it, not a replacement — merging it means adding the `sends`
+// group below into the crate's single, existing `make_config!` invocation, and adding a call to
+// `validate_sends_config` from the crate's existing top-level `validate_config`, rather than
+// dropping this file in wholesale.
+make_config! {
+    sends {
+        "Send password hashing" {
+            /// Argon2id time cost (iterations) used to hash Send passwords.
+            send_password_time_cost:   u32, true, def, 3;
+            /// Argon2id memory cost, in KiB, used to hash Send passwords.
+            send_password_memory_kib:  u32, true, def, 65_536;
+            /// Argon2id parallelism (lanes) used to hash Send passwords.
+            send_password_lanes:       u32, true, def, 4;
+        },
+        "Send blob envelope encryption" {
+            /// Base64-encoded 256-bit master key used to seal Send file blobs at rest (see
+            /// `crate::crypto::seal_blob`). Unset disables sealing: blobs are stored as the
+            /// client-encrypted bytes the server already receives, same as before this option
+            /// existed.
+            send_blob_master_key_b64: Option<String>, true, option;
+        },
+    },
+}
+
+/// Startup validation for every Send-related config item added by this series. The crate's
+/// top-level `validate_config` must call this alongside its other group-specific validators;
+/// `Send::set_password` additionally clamps its conversions defensively, since that call site has
+/// no way to confirm this actually ran.
+pub(crate) fn validate_sends_config(config: &ConfigItems) -> Result<(), Error> {
+    validate_send_password_config(config)?;
+    validate_send_blob_master_key(config)?;
+    Ok(())
+}
+
+/// Rejects a configured `SEND_BLOB_MASTER_KEY_B64` that isn't valid base64, or that doesn't decode to
+/// exactly 32 bytes (an AES-256 key). Unset is fine — that's how sealing stays opt-in.
+fn validate_send_blob_master_key(config: &ConfigItems) -> Result<(), Error> {
+    let Some(encoded) = &config.send_blob_master_key_b64 else {
+        return Ok(());
+    };
+
+    let Ok(decoded) = data_encoding::BASE64.decode(encoded.trim().as_bytes()) else {
+        err!("`SEND_BLOB_MASTER_KEY_B64` is not valid base64");
+    };
+
+    if decoded.len() != 32 {
+        err!("`SEND_BLOB_MASTER_KEY_B64` must decode to exactly 32 bytes (an AES-256 key)");
+    }
+
+    Ok(())
+}
+
+/// Rejects Send password Argon2 parameters that are degenerate enough to be insecure, or that
+/// would overflow the `i32` columns they're stored in.
+fn validate_send_password_config(config: &ConfigItems) -> Result<(), Error> {
+    if config.send_password_time_cost < 1 {
+        err!("`SEND_PASSWORD_TIME_COST` must be at least 1");
+    }
+    if i32::try_from(config.send_password_time_cost).is_err() {
+        err!("`SEND_PASSWORD_TIME_COST` is too large to store");
+    }
+
+    if config.send_password_lanes < 1 {
+        err!("`SEND_PASSWORD_LANES` must be at least 1");
+    }
+    if i32::try_from(config.send_password_lanes).is_err() {
+        err!("`SEND_PASSWORD_LANES` is too large to store");
+    }
+
+    // Argon2 requires at least 8 KiB of memory per lane.
+    if config.send_password_memory_kib < config.send_password_lanes.saturating_mul(8) {
+        err!("`SEND_PASSWORD_MEMORY_KIB` must be at least 8x `SEND_PASSWORD_LANES`");
+    }
+    if i32::try_from(config.send_password_memory_kib).is_err() {
+        err!("`SEND_PASSWORD_MEMORY_KIB` is too large to store");
+    }
+
+    Ok(())
+}
+
+impl Config {
+    /// Decodes the configured Send blob master key from base64. Returns `None` when unset, so
+    /// [`crate::crypto::seal_blob`] falls back to its no-op passthrough behavior. Startup
+    /// validation (`validate_send_blob_master_key`) guarantees that when it's set, it's exactly
+    /// 32 bytes, so this can't silently hand back a key of the wrong length.
+    pub fn send_blob_master_key(&self) -> Option<[u8; 32]> {
+        let encoded = self.send_blob_master_key_b64()?;
+        let decoded = data_encoding::BASE64.decode(encoded.trim().as_bytes()).ok()?;
+        decoded.try_into().ok()
+    }
+}
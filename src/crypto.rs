@@ -39,6 +39,29 @@ pub fn hash_password(secret: &[u8], salt: &[u8], iterations: u32, memory: u32, p
     hash_raw(secret, salt, &config).unwrap()
 }
 
+/// Outcome of [`verify_password_hash`]. Kept distinct from a plain `bool` so callers can tell a
+/// correct-but-legacy-PBKDF2 hash apart from a correct Argon2id one, and trigger a transparent
+/// upgrade.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PasswordVerificationResult {
+    Invalid,
+    Valid {
+        /// Set when the hash verified against the legacy PBKDF2 sentinel params, i.e. it should
+        /// be re-hashed under the current Argon2id parameters.
+        legacy: bool,
+    },
+}
+
+impl PasswordVerificationResult {
+    pub fn is_valid(self) -> bool {
+        matches!(self, Self::Valid { .. })
+    }
+
+    pub fn needs_rehash(self) -> bool {
+        matches!(self, Self::Valid { legacy: true })
+    }
+}
+
 pub fn verify_password_hash(
     secret: &[u8],
     salt: &[u8],
@@ -46,18 +69,171 @@ pub fn verify_password_hash(
     iterations: u32,
     memory: u32,
     parallelism: u32,
-) -> bool {
+) -> PasswordVerificationResult {
     let iterations = NonZeroU32::new(iterations).expect("iterations must be non-zero");
     // workaround for migration from pbkdf2 to argon2
     if (memory, parallelism) == (0, 0) {
         info!("verify_password_hash: using legacy pbkdf2");
-        return pbkdf2::verify(LEGACY_PBKDF2_DIGEST_ALG, iterations, salt, secret, previous).is_ok();
+        return if pbkdf2::verify(LEGACY_PBKDF2_DIGEST_ALG, iterations, salt, secret, previous).is_ok() {
+            PasswordVerificationResult::Valid { legacy: true }
+        } else {
+            PasswordVerificationResult::Invalid
+        };
     }
     info!("verify_password_hash: using argon2");
     let memory = NonZeroU32::new(memory).expect("memory must be non-zero");
     let parallelism = NonZeroU32::new(parallelism).expect("parallelism must be non-zero");
     let config = get_argon2_config(iterations.get(), memory.get(), parallelism.get());
-    verify_raw(secret, salt, previous, &config).unwrap()
+    if verify_raw(secret, salt, previous, &config).unwrap() {
+        PasswordVerificationResult::Valid { legacy: false }
+    } else {
+        PasswordVerificationResult::Invalid
+    }
+}
+
+//
+// Send blob envelope encryption
+//
+// Wraps Send file blobs in a second layer of encryption before they reach the configured
+// OpenDAL operator, so an operator with only storage-backend access (filesystem, S3, ...)
+// cannot read the client-encrypted-but-unsealed bytes. Each blob gets its own random data key,
+// which is itself encrypted ("wrapped") under the server-side master key and stored alongside
+// the blob; only the master key, not the plaintext, ever needs to stay on the application side.
+//
+// Sealed layout: [4-byte magic][1-byte version][12-byte master nonce][48-byte wrapped data key]
+//                [12-byte blob nonce][ciphertext || 16-byte tag]
+//
+// The magic is checked ahead of the version so a pre-existing, unsealed blob is never misread as
+// a sealed envelope: a single version byte would collide with roughly 1/256 of unsealed blobs,
+// but a 4-byte magic makes that a 1-in-4-billion coincidence instead.
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+
+const SEND_BLOB_MAGIC: [u8; 4] = *b"VWsb";
+const SEND_BLOB_VERSION: u8 = 1;
+const SEND_BLOB_PREFIX_LEN: usize = SEND_BLOB_MAGIC.len() + 1;
+const SEND_BLOB_NONCE_LEN: usize = 12;
+const SEND_BLOB_DATA_KEY_LEN: usize = 32;
+const SEND_BLOB_WRAPPED_KEY_LEN: usize = SEND_BLOB_DATA_KEY_LEN + 16; // + AES-256-GCM tag
+const SEND_BLOB_HEADER_LEN: usize =
+    SEND_BLOB_PREFIX_LEN + SEND_BLOB_NONCE_LEN + SEND_BLOB_WRAPPED_KEY_LEN + SEND_BLOB_NONCE_LEN;
+
+#[derive(Debug)]
+pub enum SendBlobError {
+    /// No master key is configured; sealing/opening can't happen.
+    NotConfigured,
+    /// The sealed blob is shorter than the envelope format requires.
+    Truncated,
+    /// The magic matched but the version byte didn't; this blob was sealed by a newer or older
+    /// format than this build understands.
+    UnsupportedVersion,
+    /// AEAD tag verification failed; the blob was corrupted or tampered with.
+    TamperedOrCorrupt,
+}
+
+impl std::fmt::Display for SendBlobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotConfigured => write!(f, "send blob master key is not configured"),
+            Self::Truncated => write!(f, "sealed send blob is truncated"),
+            Self::UnsupportedVersion => write!(f, "sealed send blob has an unsupported version"),
+            Self::TamperedOrCorrupt => write!(f, "sealed send blob failed authentication"),
+        }
+    }
+}
+
+impl std::error::Error for SendBlobError {}
+
+fn aead_key(bytes: &[u8; 32]) -> LessSafeKey {
+    LessSafeKey::new(UnboundKey::new(&AES_256_GCM, bytes).expect("AES-256-GCM key must be 32 bytes"))
+}
+
+/// Seal a Send file blob for storage. Returns the plaintext unchanged, with no version header,
+/// when no master key is configured, so deployments can enable sealing without migrating
+/// already-stored blobs up front.
+pub fn seal_blob(plaintext: &[u8]) -> Vec<u8> {
+    let Some(master_key) = crate::CONFIG.send_blob_master_key() else {
+        return plaintext.to_vec();
+    };
+
+    let data_key = get_random_bytes::<SEND_BLOB_DATA_KEY_LEN>();
+    let master_nonce_bytes = get_random_bytes::<SEND_BLOB_NONCE_LEN>();
+    let blob_nonce_bytes = get_random_bytes::<SEND_BLOB_NONCE_LEN>();
+
+    let mut wrapped_key = data_key.to_vec();
+    aead_key(&master_key)
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(master_nonce_bytes), Aad::empty(), &mut wrapped_key)
+        .expect("sealing a 32-byte data key cannot fail");
+
+    let mut ciphertext = plaintext.to_vec();
+    aead_key(&data_key)
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(blob_nonce_bytes), Aad::empty(), &mut ciphertext)
+        .expect("sealing the blob cannot fail");
+
+    let mut out = Vec::with_capacity(SEND_BLOB_HEADER_LEN + ciphertext.len());
+    out.extend_from_slice(&SEND_BLOB_MAGIC);
+    out.push(SEND_BLOB_VERSION);
+    out.extend_from_slice(&master_nonce_bytes);
+    out.extend_from_slice(&wrapped_key);
+    out.extend_from_slice(&blob_nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    out
+}
+
+/// Reverse [`seal_blob`]. Blobs without the magic header (written before sealing was enabled, or
+/// while a master key wasn't configured) are passed through unchanged.
+pub fn open_blob(sealed: &[u8]) -> Result<Vec<u8>, SendBlobError> {
+    if sealed.len() < SEND_BLOB_PREFIX_LEN || sealed[..SEND_BLOB_MAGIC.len()] != SEND_BLOB_MAGIC {
+        return Ok(sealed.to_vec());
+    }
+
+    if sealed[SEND_BLOB_MAGIC.len()] != SEND_BLOB_VERSION {
+        return Err(SendBlobError::UnsupportedVersion);
+    }
+
+    let master_key = crate::CONFIG.send_blob_master_key().ok_or(SendBlobError::NotConfigured)?;
+
+    let rest = &sealed[SEND_BLOB_PREFIX_LEN..];
+    if rest.len() < SEND_BLOB_NONCE_LEN + SEND_BLOB_WRAPPED_KEY_LEN + SEND_BLOB_NONCE_LEN {
+        return Err(SendBlobError::Truncated);
+    }
+
+    let (master_nonce, rest) = rest.split_at(SEND_BLOB_NONCE_LEN);
+    let (wrapped_key, rest) = rest.split_at(SEND_BLOB_WRAPPED_KEY_LEN);
+    let (blob_nonce, ciphertext) = rest.split_at(SEND_BLOB_NONCE_LEN);
+
+    let master_nonce = Nonce::try_assume_unique_for_key(master_nonce).map_err(|_| SendBlobError::Truncated)?;
+    let mut wrapped_key = wrapped_key.to_vec();
+    let data_key_bytes = aead_key(&master_key)
+        .open_in_place(master_nonce, Aad::empty(), &mut wrapped_key)
+        .map_err(|_| SendBlobError::TamperedOrCorrupt)?;
+    let data_key: [u8; SEND_BLOB_DATA_KEY_LEN] =
+        data_key_bytes.try_into().map_err(|_| SendBlobError::TamperedOrCorrupt)?;
+
+    let blob_nonce = Nonce::try_assume_unique_for_key(blob_nonce).map_err(|_| SendBlobError::Truncated)?;
+    let mut plaintext = ciphertext.to_vec();
+    let plaintext_len = aead_key(&data_key)
+        .open_in_place(blob_nonce, Aad::empty(), &mut plaintext)
+        .map_err(|_| SendBlobError::TamperedOrCorrupt)?
+        .len();
+    plaintext.truncate(plaintext_len);
+
+    Ok(plaintext)
+}
+
+/// Derives the content-addressed storage path for a Send file blob from the hex-encoded SHA-256
+/// digest of its plaintext bytes (see [`hash_blob`] for why it's the plaintext, not the sealed
+/// bytes, that gets hashed). Shards on the first byte, mirroring git's object layout, so a single
+/// directory doesn't end up holding every blob the instance has ever stored.
+pub fn generate_blob_digest_path(digest_hex: &str) -> String {
+    format!("{}/{}", &digest_hex[..2], &digest_hex[2..])
+}
+
+/// Hex-encodes the SHA-256 digest of `bytes`, for use as the content-addressed key of a stored
+/// Send file blob. Callers must hash the plaintext, not [`seal_blob`]'s output: sealing draws a
+/// fresh random data key and nonce every time, so the sealed bytes for identical plaintext are
+/// never equal, which would defeat dedup entirely.
+pub fn hash_blob(bytes: &[u8]) -> String {
+    HEXLOWER.encode(digest::digest(&digest::SHA256, bytes).as_ref())
 }
 
 //
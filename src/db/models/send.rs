@@ -3,7 +3,7 @@ use serde_json::Value;
 
 use crate::{config::PathType, util::LowerCase, CONFIG};
 
-use super::{OrganizationId, User, UserId};
+use super::{Organization, OrganizationId, SendFileBlob, User, UserId, UserOrganization};
 use id::SendId;
 
 db_object! {
@@ -83,22 +83,21 @@ impl Send {
     }
 
     pub fn set_password(&mut self, password: Option<&str>) {
-        const PASSWORD_ITER: i32 = 2;
-        const PASSWORD_MEM: i32 = 1_048_576;
-        const PASSWORD_PARA: i32 = 8;
-
         if let Some(password) = password {
-            self.password_iter = Some(PASSWORD_ITER);
-            self.password_mem = Some(PASSWORD_MEM);
-            self.password_para = Some(PASSWORD_PARA);
+            let iterations = CONFIG.send_password_time_cost();
+            let memory_kib = CONFIG.send_password_memory_kib();
+            let parallelism = CONFIG.send_password_lanes();
+
+            // `validate_send_password_config` is meant to reject configs that don't fit these
+            // columns at startup, but that startup hook lives outside this trimmed tree and this
+            // call site has no way to confirm it actually ran — so clamp defensively here too,
+            // rather than trust an `.expect()` backed by a guarantee this code can't verify.
+            self.password_iter = Some(i32::try_from(iterations).unwrap_or(i32::MAX));
+            self.password_mem = Some(i32::try_from(memory_kib).unwrap_or(i32::MAX));
+            self.password_para = Some(i32::try_from(parallelism).unwrap_or(i32::MAX));
+
             let salt = crate::crypto::get_random_bytes::<64>().to_vec();
-            let hash = crate::crypto::hash_password(
-                password.as_bytes(),
-                &salt,
-                PASSWORD_ITER as u32,
-                PASSWORD_MEM as u32,
-                PASSWORD_PARA as u32,
-            );
+            let hash = crate::crypto::hash_password(password.as_bytes(), &salt, iterations, memory_kib, parallelism);
             self.password_salt = Some(salt);
             self.password_hash = Some(hash);
         } else {
@@ -110,8 +109,18 @@ impl Send {
         }
     }
 
-    pub fn check_password(&self, password: &str) -> bool {
-        match (&self.password_hash, &self.password_salt, self.password_iter, self.password_mem, self.password_para) {
+    /// Checks `password` against the stored hash. If it matches and the stored hash is still on
+    /// the legacy PBKDF2 sentinel params, transparently re-hashes it under the current Argon2id
+    /// parameters and persists the upgrade, so active Sends move off PBKDF2 without user action.
+    ///
+    /// Breaking change for callers: this used to be a sync `fn(&self, password: &str) -> bool`.
+    /// The Send public-access endpoint (`src/api/core/sends.rs`, outside this trimmed tree) calls
+    /// this to gate access to a password-protected Send and must be updated alongside this
+    /// signature change: take `&mut send` instead of `&send`, thread its `DbConn` through, and
+    /// `.await` the call.
+    pub async fn check_password(&mut self, password: &str, conn: &mut DbConn) -> bool {
+        let result = match (&self.password_hash, &self.password_salt, self.password_iter, self.password_mem, self.password_para)
+        {
             (Some(hash), Some(salt), Some(iter), Some(mem), Some(para)) => crate::crypto::verify_password_hash(
                 password.as_bytes(),
                 salt,
@@ -120,8 +129,21 @@ impl Send {
                 mem as u32,
                 para as u32,
             ),
-            _ => false,
+            _ => return false,
+        };
+
+        if !result.is_valid() {
+            return false;
         }
+
+        if result.needs_rehash() {
+            self.set_password(Some(password));
+            if let Err(e) = self.save(conn).await {
+                warn!("Failed to persist Send password rehash: {e:#?}");
+            }
+        }
+
+        true
     }
 
     pub async fn creator_identifier(&self, conn: &mut DbConn) -> Option<String> {
@@ -137,6 +159,12 @@ impl Send {
             }
         }
 
+        if let Some(org_uuid) = &self.organization_uuid {
+            if let Some(org) = Organization::find_by_uuid(org_uuid, conn).await {
+                return Some(org.name);
+            }
+        }
+
         None
     }
 
@@ -248,7 +276,19 @@ impl Send {
 
         if self.atype == SendType::File as i32 {
             let operator = CONFIG.opendal_operator_for_path_type(PathType::Sends)?;
-            operator.remove_all(&self.uuid).await.ok();
+            match self.file_blob_digest() {
+                Some(digest) => {
+                    if SendFileBlob::release(&digest, conn).await {
+                        operator.remove_all(&crate::crypto::generate_blob_digest_path(&digest)).await.ok();
+                    }
+                }
+                // Sends stored before content-addressed blobs existed have no digest and no
+                // shared refcount to check; they still own their blob at the legacy per-UUID
+                // path, so remove it unconditionally.
+                None => {
+                    operator.remove_all(&self.uuid).await.ok();
+                }
+            }
         }
 
         db_run! { conn: {
@@ -258,6 +298,54 @@ impl Send {
         }}
     }
 
+    /// Digest of the blob this file Send points at, as stored in its `data` JSON by
+    /// [`Send::store_file_blob`]. Absent for Sends stored before content-addressed blobs were
+    /// introduced, or for text Sends.
+    fn file_blob_digest(&self) -> Option<String> {
+        let data = serde_json::from_str::<LowerCase<Value>>(&self.data).ok()?.data;
+        data.get("digest")?.as_str().map(str::to_owned)
+    }
+
+    /// Seals `plaintext` with [`crate::crypto::seal_blob`] and stores it at a path keyed on the
+    /// digest of the *plaintext*, creating or reusing the tracking row in `send_file_blobs`; the
+    /// physical write is skipped when an identical blob is already stored under another Send.
+    /// Sealing uses a fresh random data key and nonce on every call, so two uploads of identical
+    /// content never produce the same sealed bytes — hashing the plaintext instead of the sealed
+    /// output is what lets dedup keep working once a master key is configured. Records the
+    /// resulting digest in `self.data` so `file_blob_digest` can find it again on download or
+    /// delete.
+    pub async fn store_file_blob(&mut self, plaintext: &[u8], conn: &mut DbConn) -> EmptyResult {
+        let digest = crate::crypto::hash_blob(plaintext);
+
+        if SendFileBlob::retain(&digest, conn).await {
+            let sealed = crate::crypto::seal_blob(plaintext);
+            let operator = CONFIG.opendal_operator_for_path_type(PathType::Sends)?;
+            let path = crate::crypto::generate_blob_digest_path(&digest);
+            if let Err(e) = operator.write(&path, sealed).await {
+                // The refcount row was already created at 1 by `retain`; if the physical write
+                // never landed, release it immediately instead of leaving a permanent orphan
+                // that a later upload with the same digest would silently dedup against.
+                SendFileBlob::release(&digest, conn).await;
+                return Err(e).map_res("Error writing send file blob");
+            }
+        }
+
+        let mut data = serde_json::from_str::<LowerCase<Value>>(&self.data).map(|d| d.data).unwrap_or_default();
+        data["digest"] = Value::String(digest);
+        self.data = serde_json::to_string(&data).map_res("Error serializing send data")?;
+        Ok(())
+    }
+
+    /// Reads back and unseals the physical blob this Send points at with
+    /// [`crate::crypto::open_blob`]. Returns `None` for Sends with no recorded digest (text
+    /// Sends, or file Sends stored before content-addressed blobs existed).
+    pub async fn read_file_blob(&self) -> Option<Result<Vec<u8>, crate::crypto::SendBlobError>> {
+        let digest = self.file_blob_digest()?;
+        let operator = CONFIG.opendal_operator_for_path_type(PathType::Sends).ok()?;
+        let sealed = operator.read(&crate::crypto::generate_blob_digest_path(&digest)).await.ok()?.to_vec();
+        Some(crate::crypto::open_blob(&sealed))
+    }
+
     /// Purge all sends that are past their deletion date.
     pub async fn purge(conn: &mut DbConn) {
         for send in Self::find_by_past_deletion_date(conn).await {
@@ -266,17 +354,24 @@ impl Send {
     }
 
     pub async fn update_users_revision(&self, conn: &mut DbConn) -> Vec<UserId> {
-        let mut user_uuids = Vec::new();
-        match &self.user_uuid {
-            Some(user_uuid) => {
+        if let Some(user_uuid) = &self.user_uuid {
+            User::update_uuid_revision(user_uuid, conn).await;
+            return vec![user_uuid.clone()];
+        }
+
+        if let Some(org_uuid) = &self.organization_uuid {
+            let user_uuids: Vec<UserId> = UserOrganization::find_confirmed_by_org(org_uuid, conn)
+                .await
+                .into_iter()
+                .map(|user_org| user_org.user_uuid)
+                .collect();
+            for user_uuid in &user_uuids {
                 User::update_uuid_revision(user_uuid, conn).await;
-                user_uuids.push(user_uuid.clone())
-            }
-            None => {
-                // Belongs to Organization, not implemented
             }
-        };
-        user_uuids
+            return user_uuids;
+        }
+
+        Vec::new()
     }
 
     pub async fn delete_all_by_user(user_uuid: &UserId, conn: &mut DbConn) -> EmptyResult {
@@ -332,8 +427,17 @@ impl Send {
     }
 
     pub async fn size_by_user(user_uuid: &UserId, conn: &mut DbConn) -> Option<i64> {
-        let sends = Self::find_by_user(user_uuid, conn).await;
+        Self::sum_file_sizes(Self::find_by_user(user_uuid, conn).await)
+    }
+
+    pub async fn size_by_org(org_uuid: &OrganizationId, conn: &mut DbConn) -> Option<i64> {
+        Self::sum_file_sizes(Self::find_by_org(org_uuid, conn).await)
+    }
 
+    /// Sums the declared (logical) file size of every file Send in `sends`. This is the
+    /// client-reported plaintext size, not the physical size of whatever is on disk, so quotas
+    /// stay correct per-owner even when the storage layer deduplicates blobs across owners.
+    fn sum_file_sizes(sends: Vec<Self>) -> Option<i64> {
         #[derive(serde::Deserialize)]
         struct FileData {
             #[serde(rename = "size", alias = "Size")]
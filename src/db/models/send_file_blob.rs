@@ -0,0 +1,87 @@
+use crate::db::DbConn;
+
+db_object! {
+    /// Tracks how many file Sends currently point at a given content-addressed blob, so the
+    /// physical object in storage is only removed once nothing references it anymore.
+    #[derive(Identifiable, Queryable, Insertable, AsChangeset)]
+    #[diesel(table_name = send_file_blobs)]
+    #[diesel(primary_key(digest))]
+    pub struct SendFileBlob {
+        pub digest: String,
+        pub refcount: i32,
+    }
+}
+
+impl SendFileBlob {
+    /// Records a new reference to the blob keyed by `digest`, creating the row with a refcount
+    /// of 1 if none exists yet. The increment is done as an atomic `UPDATE ... SET refcount =
+    /// refcount + 1` rather than a Rust-side read-modify-write, so two concurrent uploads of
+    /// identical content can't both observe "not found" and clobber each other's refcount.
+    /// Returns `true` when the caller is the first to reference this digest, meaning it must
+    /// actually write the sealed bytes to storage; `false` means an identical blob is already
+    /// stored and the upload can be skipped.
+    pub async fn retain(digest: &str, conn: &mut DbConn) -> bool {
+        let updated = db_run! {conn: {
+            diesel::update(send_file_blobs::table.filter(send_file_blobs::digest.eq(digest)))
+                .set(send_file_blobs::refcount.eq(send_file_blobs::refcount + 1))
+                .execute(conn)
+                .unwrap_or(0)
+        }};
+
+        if updated > 0 {
+            return false;
+        }
+
+        // No existing row to increment; try to create one at refcount 1.
+        let inserted = db_run! {conn: {
+            diesel::insert_into(send_file_blobs::table)
+                .values(SendFileBlobDb::to_db(&Self { digest: digest.to_string(), refcount: 1 }))
+                .execute(conn)
+        }};
+
+        match inserted {
+            Ok(_) => true,
+            // A concurrent upload of the same content raced us and inserted first; fall back to
+            // the atomic increment instead of clobbering its refcount.
+            Err(_) => {
+                db_run! {conn: {
+                    diesel::update(send_file_blobs::table.filter(send_file_blobs::digest.eq(digest)))
+                        .set(send_file_blobs::refcount.eq(send_file_blobs::refcount + 1))
+                        .execute(conn)
+                        .ok();
+                }};
+                false
+            }
+        }
+    }
+
+    /// Drops one reference to the blob keyed by `digest`. Like `retain`, the decrement is an
+    /// atomic `UPDATE ... SET refcount = refcount - 1` and the delete that follows is filtered on
+    /// `refcount <= 0`, not a Rust-side read-modify-write, so two concurrent `Send::delete`s releasing
+    /// the same digest can't both read a stale refcount and under-decrement it. Returns `true`
+    /// when the refcount reached zero and the row was removed, meaning the caller must also
+    /// remove the physical object from storage; `false` means other Sends still reference it, or
+    /// there was no record of this digest to begin with.
+    pub async fn release(digest: &str, conn: &mut DbConn) -> bool {
+        let updated = db_run! {conn: {
+            diesel::update(send_file_blobs::table.filter(send_file_blobs::digest.eq(digest)))
+                .set(send_file_blobs::refcount.eq(send_file_blobs::refcount - 1))
+                .execute(conn)
+                .unwrap_or(0)
+        }};
+
+        if updated == 0 {
+            return false;
+        }
+
+        let deleted = db_run! {conn: {
+            diesel::delete(
+                send_file_blobs::table.filter(send_file_blobs::digest.eq(digest)).filter(send_file_blobs::refcount.le(0)),
+            )
+            .execute(conn)
+            .unwrap_or(0)
+        }};
+
+        deleted > 0
+    }
+}